@@ -0,0 +1,24 @@
+use palette::Srgb;
+
+/// WCAG AA's minimum contrast ratio for normal text, and the default
+/// threshold used for label color selection.
+pub const WCAG_AA_THRESHOLD: f32 = 4.5;
+
+fn linearize(channel: f32) -> f32 {
+    if channel <= 0.03928 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// WCAG relative luminance of an sRGB color, in `[0, 1]`.
+pub fn relative_luminance(color: Srgb<f32>) -> f32 {
+    0.2126 * linearize(color.red) + 0.7152 * linearize(color.green) + 0.0722 * linearize(color.blue)
+}
+
+/// WCAG contrast ratio between two relative luminances, in `[1, 21]`.
+pub fn contrast_ratio(a: f32, b: f32) -> f32 {
+    let (lighter, darker) = if a >= b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
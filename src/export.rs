@@ -0,0 +1,147 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::{hex_to_rgb, ColorEntry};
+
+/// Text/binary swatch formats the sorted palette can be exported to,
+/// alongside the PNG render.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// GIMP palette (`.gpl`)
+    Gpl,
+    /// CSS custom properties under `:root`
+    Css,
+    /// Sass/SCSS variables
+    Scss,
+    /// Normalized JSON round-trip of `ColorEntry`
+    Json,
+    /// Adobe Swatch Exchange (`.ase`)
+    Ase,
+}
+
+impl ExportFormat {
+    /// Infers a format from a file extension, if recognized.
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .as_deref()
+        {
+            Some("gpl") => Some(ExportFormat::Gpl),
+            Some("css") => Some(ExportFormat::Css),
+            Some("scss") | Some("sass") => Some(ExportFormat::Scss),
+            Some("json") => Some(ExportFormat::Json),
+            Some("ase") => Some(ExportFormat::Ase),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExportedColor<'a> {
+    name: &'a str,
+    hex: &'a str,
+}
+
+/// Writes `colors` to `path` in the given export `format`.
+pub fn export_palette(colors: &[ColorEntry], format: ExportFormat, path: &Path) -> std::io::Result<()> {
+    match format {
+        ExportFormat::Gpl => export_gpl(colors, path),
+        ExportFormat::Css => export_css(colors, path),
+        ExportFormat::Scss => export_scss(colors, path),
+        ExportFormat::Json => export_json(colors, path),
+        ExportFormat::Ase => export_ase(colors, path),
+    }
+}
+
+/// Turns a color name into a CSS/Sass-safe identifier fragment.
+fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+fn export_gpl(colors: &[ColorEntry], path: &Path) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "GIMP Palette")?;
+    writeln!(writer, "Name: lcpg palette")?;
+    writeln!(writer, "Columns: 0")?;
+    writeln!(writer, "#")?;
+    for color in colors {
+        let (r, g, b) = hex_to_rgb(&color.hex);
+        writeln!(writer, "{:>3} {:>3} {:>3}\t{}", r, g, b, color.name)?;
+    }
+    Ok(())
+}
+
+fn export_css(colors: &[ColorEntry], path: &Path) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, ":root {{")?;
+    for color in colors {
+        writeln!(writer, "  --{}: {};", slug(&color.name), color.hex)?;
+    }
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+fn export_scss(colors: &[ColorEntry], path: &Path) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for color in colors {
+        writeln!(writer, "${}: {};", slug(&color.name), color.hex)?;
+    }
+    Ok(())
+}
+
+fn export_json(colors: &[ColorEntry], path: &Path) -> std::io::Result<()> {
+    let exported: Vec<ExportedColor> = colors
+        .iter()
+        .map(|color| ExportedColor { name: &color.name, hex: &color.hex })
+        .collect();
+    let file = File::create(path)?;
+    serde_json::to_writer_pretty(file, &exported)?;
+    Ok(())
+}
+
+/// Writes an Adobe Swatch Exchange file: the `ASEF` signature, a version,
+/// a block count, then one color-entry block (`0x0001`) per swatch holding
+/// a UTF-16BE name and an `RGB ` color model with three big-endian floats.
+fn export_ase(colors: &[ColorEntry], path: &Path) -> std::io::Result<()> {
+    let mut buf: Vec<u8> = Vec::new();
+    buf.extend_from_slice(b"ASEF");
+    buf.extend_from_slice(&1u16.to_be_bytes());
+    buf.extend_from_slice(&0u16.to_be_bytes());
+
+    let block_count_pos = buf.len();
+    buf.extend_from_slice(&0u32.to_be_bytes());
+
+    for color in colors {
+        let (r, g, b) = hex_to_rgb(&color.hex);
+        let name_utf16: Vec<u16> = color.name.encode_utf16().chain(std::iter::once(0)).collect();
+
+        let mut block = Vec::new();
+        block.extend_from_slice(&(name_utf16.len() as u16).to_be_bytes());
+        for unit in &name_utf16 {
+            block.extend_from_slice(&unit.to_be_bytes());
+        }
+        block.extend_from_slice(b"RGB ");
+        block.extend_from_slice(&(r as f32 / 255.0).to_be_bytes());
+        block.extend_from_slice(&(g as f32 / 255.0).to_be_bytes());
+        block.extend_from_slice(&(b as f32 / 255.0).to_be_bytes());
+        block.extend_from_slice(&0u16.to_be_bytes()); // color type: global
+
+        buf.extend_from_slice(&0x0001u16.to_be_bytes());
+        buf.extend_from_slice(&(block.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&block);
+    }
+
+    let block_count = colors.len() as u32;
+    buf[block_count_pos..block_count_pos + 4].copy_from_slice(&block_count.to_be_bytes());
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&buf)
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use palette::{FromColor, Okhsl, Oklab, Srgb};
+
+use crate::hue::GRAY_SATURATION_THRESHOLD;
+use crate::ColorEntry;
+
+const HUE_BUCKETS: u32 = 16;
+const SAT_BINS: u32 = 4;
+const LIGHT_BINS: u32 = 5;
+const ALPHA_THRESHOLD: u8 = 16;
+
+/// Images larger than this along their longest edge are subsampled so
+/// extraction stays fast; the stride is picked so roughly this many pixels
+/// are sampled along that edge.
+const MAX_SAMPLED_DIMENSION: u32 = 256;
+
+/// Running pixel count and summed Oklab coordinates for one quantization
+/// bucket, averaged back to a color once all pixels have been scanned.
+#[derive(Default, Clone, Copy)]
+struct Bucket {
+    count: u64,
+    sum_l: f64,
+    sum_a: f64,
+    sum_b: f64,
+}
+
+impl Bucket {
+    fn accumulate(&mut self, oklab: Oklab) {
+        self.count += 1;
+        self.sum_l += oklab.l as f64;
+        self.sum_a += oklab.a as f64;
+        self.sum_b += oklab.b as f64;
+    }
+
+    fn average(&self) -> Oklab {
+        let n = self.count.max(1) as f32;
+        Oklab::new(
+            (self.sum_l as f32) / n,
+            (self.sum_a as f32) / n,
+            (self.sum_b as f32) / n,
+        )
+    }
+}
+
+/// Bucket key a pixel is quantized into: hue-bucketed chromatic colors use
+/// `(Some(hue_bucket), sat_bin, light_bin)`; near-gray pixels are hue-less
+/// and fall back to `(None, 0, light_bin)`.
+type BucketKey = (Option<u32>, u32, u32);
+
+fn bin(value: f32, bins: u32) -> u32 {
+    (value.clamp(0.0, 1.0) * (bins - 1) as f32).round() as u32
+}
+
+fn hue_bucket(okhsl: Okhsl) -> Option<u32> {
+    if okhsl.saturation < GRAY_SATURATION_THRESHOLD {
+        return None;
+    }
+    let step = 360.0 / HUE_BUCKETS as f32;
+    let mut hue = okhsl.hue.into_degrees();
+    if hue < 0.0 {
+        hue += 360.0;
+    }
+    Some((hue / step).round() as u32 % HUE_BUCKETS)
+}
+
+fn bucket_key(okhsl: Okhsl) -> BucketKey {
+    let light_bin = bin(okhsl.lightness, LIGHT_BINS);
+    match hue_bucket(okhsl) {
+        Some(hue) => (Some(hue), bin(okhsl.saturation, SAT_BINS), light_bin),
+        None => (None, 0, light_bin),
+    }
+}
+
+/// Stride that keeps the sampled grid roughly `MAX_SAMPLED_DIMENSION` pixels
+/// along the image's longer edge.
+fn subsample_stride(width: u32, height: u32) -> u32 {
+    ((width.max(height) + MAX_SAMPLED_DIMENSION - 1) / MAX_SAMPLED_DIMENSION).max(1)
+}
+
+/// File extensions treated as raster images rather than the JSON
+/// `ColorEntry` list.
+pub fn is_image_extension(path: &Path) -> bool {
+    matches!(
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .as_deref(),
+        Some("png") | Some("jpg") | Some("jpeg") | Some("bmp") | Some("gif") | Some("webp") | Some("tiff")
+    )
+}
+
+/// Extracts a `Vec<ColorEntry>` from a raster image by quantizing pixels
+/// into OkHsl hue/saturation/lightness buckets, keeping the `top_k` most
+/// common buckets and averaging each one in Oklab for a perceptually even
+/// swatch color.
+pub fn extract_palette(path: &Path, top_k: usize) -> Vec<ColorEntry> {
+    let img = image::open(path).expect("Failed to open image").to_rgba8();
+    let (width, height) = img.dimensions();
+    let stride = subsample_stride(width, height);
+
+    let mut buckets: HashMap<BucketKey, Bucket> = HashMap::new();
+
+    for y in (0..height).step_by(stride as usize) {
+        for x in (0..width).step_by(stride as usize) {
+            let [r, g, b, a] = img.get_pixel(x, y).0;
+            if a < ALPHA_THRESHOLD {
+                continue;
+            }
+
+            let srgb = Srgb::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+            let okhsl = Okhsl::from_color(srgb);
+            let oklab = Oklab::from_color(srgb);
+            buckets.entry(bucket_key(okhsl)).or_default().accumulate(oklab);
+        }
+    }
+
+    // `HashMap` iteration order is randomized per-process, so break ties on
+    // `count` by the bucket key itself; otherwise which bucket survives
+    // `truncate` at the `top_k` boundary would vary run to run for the same
+    // image, which flat-color/illustration inputs hit constantly.
+    let mut ranked: Vec<(BucketKey, Bucket)> = buckets.into_iter().collect();
+    ranked.sort_by(|(key_a, a), (key_b, b)| b.count.cmp(&a.count).then_with(|| key_a.cmp(key_b)));
+    ranked.truncate(top_k);
+
+    ranked
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, bucket))| {
+            let srgb: Srgb<u8> = Srgb::from_color(bucket.average()).into_format();
+            ColorEntry {
+                name: format!("Color {}", i + 1),
+                hex: format!("#{:02X}{:02X}{:02X}", srgb.red, srgb.green, srgb.blue),
+            }
+        })
+        .collect()
+}
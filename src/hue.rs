@@ -0,0 +1,40 @@
+/// Precision used when storing a hue's normalized ratio as a stable-ordering `u32`.
+const RATIO_SCALE: u32 = 1_000_000;
+
+/// Saturation below this is treated as gray (hue-less), independent of hue.
+/// Shared by every module that needs to tell chromatic colors from grays.
+pub const GRAY_SATURATION_THRESHOLD: f32 = 0.05;
+
+/// A hue angle normalized to a ratio in `[0, 1)`, stored as a scaled `u32`
+/// so hues can be hashed, sorted, and compared without floating-point
+/// quirks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hue(u32);
+
+impl Hue {
+    /// Builds a `Hue` from an angle in degrees, wrapping negative angles by
+    /// adding a full turn first.
+    pub fn from_degrees(mut degrees: f32) -> Self {
+        degrees %= 360.0;
+        if degrees < 0.0 {
+            degrees += 360.0;
+        }
+        let ratio = degrees / 360.0;
+        Hue(((ratio * RATIO_SCALE as f32).round() as u32) % RATIO_SCALE)
+    }
+
+    pub fn ratio(self) -> f32 {
+        self.0 as f32 / RATIO_SCALE as f32
+    }
+
+    /// Snaps this hue to the nearest of `buckets` evenly spaced buckets,
+    /// returning the bucket index. `buckets` of `0` has no meaningful
+    /// bucket to snap into, so it returns `0` rather than dividing by zero.
+    pub fn snap(self, buckets: u32) -> u32 {
+        if buckets == 0 {
+            return 0;
+        }
+        let bucket_width = 1.0 / buckets as f32;
+        ((self.ratio() / bucket_width).round() as u32) % buckets
+    }
+}
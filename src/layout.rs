@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+use palette::{FromColor, Hsl, Okhsl, Srgb};
+
+use crate::hue::{Hue, GRAY_SATURATION_THRESHOLD};
+use crate::{hex_to_rgb, ColorEntry};
+
+fn lightness(color: &ColorEntry) -> f32 {
+    let (r, g, b) = hex_to_rgb(&color.hex);
+    Hsl::from_color(Srgb::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)).lightness
+}
+
+/// Groups colors into hue-family columns by snapping each color's OkHsl hue
+/// into one of `bucket_count` buckets. Each occupied hue bucket becomes its
+/// own column, ordered by hue; near-gray colors are collected into a
+/// dedicated trailing column. Every column is sorted by lightness, so
+/// reading down a column walks a single hue from dark to light.
+pub fn hue_columns(colors: Vec<ColorEntry>, bucket_count: u32) -> Vec<Vec<ColorEntry>> {
+    let mut by_bucket: BTreeMap<u32, Vec<ColorEntry>> = BTreeMap::new();
+    let mut grays: Vec<ColorEntry> = Vec::new();
+
+    for color in colors {
+        let (r, g, b) = hex_to_rgb(&color.hex);
+        let srgb = Srgb::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+        let okhsl = Okhsl::from_color(srgb);
+
+        if okhsl.saturation < GRAY_SATURATION_THRESHOLD {
+            grays.push(color);
+        } else {
+            let hue = Hue::from_degrees(okhsl.hue.into_degrees());
+            by_bucket.entry(hue.snap(bucket_count)).or_default().push(color);
+        }
+    }
+
+    let mut columns: Vec<Vec<ColorEntry>> = by_bucket
+        .into_values()
+        .map(|mut column| {
+            column.sort_by(|a, b| lightness(a).partial_cmp(&lightness(b)).unwrap());
+            column
+        })
+        .collect();
+
+    if !grays.is_empty() {
+        grays.sort_by(|a, b| lightness(a).partial_cmp(&lightness(b)).unwrap());
+        columns.push(grays);
+    }
+
+    columns
+}
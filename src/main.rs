@@ -10,19 +10,61 @@ use acap::euclid::Euclidean;
 use acap::Proximity;
 use clap::Parser;
 
-const BLOCK_SIZE_X: u32 = 400;
-const BLOCK_SIZE_Y: u32 = 300;
+mod contrast;
+mod export;
+mod extract;
+mod hue;
+mod layout;
+mod shapes;
+mod theme;
 
-const COLUMNS: usize = 8;
+use contrast::{contrast_ratio, relative_luminance, WCAG_AA_THRESHOLD};
+use theme::{Background, Theme};
 
 #[derive(Parser)]
 struct Cli {
-    /// The path to the file for color entry
+    /// The path to the file for color entry, or an image (detected by extension, or forced with `--extract`)
     input: std::path::PathBuf,
 
     // Output file name
     #[arg(short, long, default_value = "palette.png")]
-    output: String
+    output: String,
+
+    /// Treat `input` as a raster image and extract a palette from it instead of reading JSON
+    #[arg(long)]
+    extract: bool,
+
+    /// Number of colors to keep when extracting a palette from an image
+    #[arg(long, default_value_t = 16)]
+    top_colors: usize,
+
+    /// Lay out columns by hue family (via OkHsl hue snapping) instead of the fixed grid
+    #[arg(long)]
+    hue_columns: bool,
+
+    /// Number of hue buckets to snap into when `--hue-columns` is set
+    #[arg(long, default_value_t = 12, value_parser = clap::value_parser!(u32).range(1..))]
+    hue_bucket_count: u32,
+
+    /// Minimum WCAG contrast ratio a label color must clear against its swatch
+    #[arg(long, default_value_t = WCAG_AA_THRESHOLD)]
+    label_contrast_threshold: f32,
+
+    /// Optional path to also export the sorted palette as a swatch file (.gpl/.css/.scss/.json/.ase)
+    #[arg(long)]
+    export: Option<std::path::PathBuf>,
+
+    /// Export format; inferred from `--export`'s extension when omitted
+    #[arg(long, value_enum)]
+    format: Option<export::ExportFormat>,
+
+    /// Optional theme config file (TOML or JSON) overriding layout, background, font and swatch styling
+    #[arg(long)]
+    config: Option<std::path::PathBuf>,
+
+    /// Draw anti-aliased rounded-corner swatches with a soft drop shadow, overriding the theme
+    #[arg(long)]
+    rounded_swatches: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -31,50 +73,46 @@ struct ColorEntry {
     hex: String
 }
 
-fn luminance(hsl: Hsl) -> f32 {
-    let rgb: Srgb<f32> = Srgb::from_color(hsl);
-    0.299 * rgb.red + 0.587 * rgb.green + 0.114 * rgb.blue
-}
-
-pub fn pick_label_color(bg: Srgb<f32>) -> (u8,u8,u8) {
+/// Picks a label color for text drawn over `bg`: generates a handful of
+/// candidates (a lightened and a darkened variant of `bg`, plus pure white
+/// and near-black), scores each by WCAG contrast ratio against `bg`, and
+/// keeps the lowest-contrast candidate that still clears `min_contrast` (so
+/// the label blends in as much as legibility allows), falling back to the
+/// highest-contrast candidate if none clear the threshold.
+pub fn pick_label_color(bg: Srgb<f32>, min_contrast: f32) -> (u8, u8, u8) {
     let hsl_bg: Hsl = Hsl::from_color(bg);
-    let mut hue = hsl_bg.hue.into_degrees();
-    if hue < 0.0 {
-        hue += 360.0;
-    }
-    let mut hsl_label = hsl_bg;
-    hsl_label.saturation *= 0.5;
-    let mut lightened = hsl_label;
+    let mut desaturated = hsl_bg;
+    desaturated.saturation *= 0.5;
+    let mut lightened = desaturated;
     lightened.lightness = 0.775;
-    let mut darkened = hsl_label;
+    let mut darkened = desaturated;
     darkened.lightness = 0.28;
-    let l_bg = luminance(hsl_bg);
-    let visually_dark_bg = l_bg < 0.62 && hsl_bg.saturation * hsl_bg.lightness > 0.1;
-
-    // Force light label for certain hues when background is dark
-    let hue_prefers_light = (36.0..=80.0).contains(&hue)   // yellow, chartreuse
-                         || (90.0..=185.0).contains(&hue) // greenish tones
-                         || (300.0..=340.0).contains(&hue);  //purples
-    let l_light = luminance(lightened);
-    let l_dark = luminance(darkened);
-    let used = if visually_dark_bg && hue_prefers_light {
-        "light"
-    } else if (l_light - l_bg).abs() > (l_dark - l_bg).abs() {
-        "light"
-    } else {
-        "dark "
-    };
 
-    let chosen = if used == "light" {
-        lightened
-    } else {
-        darkened
-    };
-    let adjusted: Srgb<u8> = Srgb::from_color(chosen).into_format();
+    let candidates: [Srgb<f32>; 4] = [
+        Srgb::from_color(lightened),
+        Srgb::from_color(darkened),
+        Srgb::new(1.0, 1.0, 1.0),
+        Srgb::new(0.05, 0.05, 0.05),
+    ];
+
+    let bg_luminance = relative_luminance(bg);
+    let scored = candidates
+        .into_iter()
+        .map(|candidate| (candidate, contrast_ratio(relative_luminance(candidate), bg_luminance)));
+
+    let chosen = scored
+        .clone()
+        .filter(|(_, ratio)| *ratio >= min_contrast)
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .or_else(|| scored.max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap()))
+        .map(|(candidate, _)| candidate)
+        .unwrap();
+
+    let adjusted: Srgb<u8> = chosen.into_format();
     (adjusted.red, adjusted.green, adjusted.blue)
 }
 
-fn hex_to_rgb(hex: &str) -> (u8, u8, u8) {
+pub(crate) fn hex_to_rgb(hex: &str) -> (u8, u8, u8) {
     let hex = hex.trim_start_matches('#');
     let r = u8::from_str_radix(&hex[0..2], 16).unwrap();
     let g = u8::from_str_radix(&hex[2..4], 16).unwrap();
@@ -149,6 +187,10 @@ fn draw_centered_text(
 }
 
 fn sort_colors(colors: Vec<ColorEntry>) -> Vec<ColorEntry>{
+    if colors.is_empty() {
+        return colors;
+    }
+
     let hsl_coords: Vec<Euclidean<[f32; 3]>> = colors.iter()
     .map(|color| {
         let (r, g, b) = hex_to_rgb(&color.hex);
@@ -203,63 +245,155 @@ fn sort_colors(colors: Vec<ColorEntry>) -> Vec<ColorEntry>{
 
 fn main() {
     let args = Cli::parse();
+    let mut theme = Theme::load(args.config.as_deref());
+    if args.rounded_swatches {
+        theme.rounded_swatches = true;
+    }
 
-    // Load JSON file
-    let entry_file = File::open(args.input).expect("Failed to open file");
-    let entry_reader = BufReader::new(entry_file);
-
-    // Make and sort color entry vector
-    let colors: Vec<ColorEntry> = serde_json::from_reader(entry_reader).expect("Failed to parse JSON");
+    // Load colors, either from a JSON ColorEntry list or by extracting a palette from an image
+    let colors: Vec<ColorEntry> = if args.extract || extract::is_image_extension(&args.input) {
+        extract::extract_palette(&args.input, args.top_colors)
+    } else {
+        let entry_file = File::open(&args.input).expect("Failed to open file");
+        let entry_reader = BufReader::new(entry_file);
+        serde_json::from_reader(entry_reader).expect("Failed to parse JSON")
+    };
+    if colors.is_empty() {
+        eprintln!("No colors to render: input produced an empty palette");
+        std::process::exit(1);
+    }
     let sorted_colors = sort_colors(colors.clone());
-    
+
+    // Assign each color a (column, row) position, either via the fixed grid
+    // or, with `--hue-columns`, one column per hue family
+    let (placements, num_columns): (Vec<(u32, u32, ColorEntry)>, usize) = if args.hue_columns {
+        let columns = layout::hue_columns(sorted_colors, args.hue_bucket_count);
+        let placements = columns
+            .into_iter()
+            .enumerate()
+            .flat_map(|(col, column)| {
+                column
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(row, color)| (col as u32, row as u32, color))
+            })
+            .collect::<Vec<_>>();
+        let num_columns = placements.iter().map(|(col, _, _)| col + 1).max().unwrap_or(0) as usize;
+        (placements, num_columns)
+    } else {
+        let placements = sorted_colors
+            .into_iter()
+            .enumerate()
+            .map(|(i, color)| ((i % theme.columns) as u32, (i / theme.columns) as u32, color))
+            .collect();
+        (placements, theme.columns)
+    };
+
+    // Optionally export the sorted palette to a standard swatch format
+    if let Some(export_path) = &args.export {
+        let format = args
+            .format
+            .or_else(|| export::ExportFormat::from_extension(export_path))
+            .unwrap_or(export::ExportFormat::Json);
+        let export_colors: Vec<ColorEntry> = placements.iter().map(|(_, _, color)| color.clone()).collect();
+        export::export_palette(&export_colors, format, export_path).expect("Failed to export palette");
+        println!("Exported {} colors to {}", export_colors.len(), export_path.display());
+    }
+
     // Calculate layout
-    let rows = (colors.len() + COLUMNS - 1) / COLUMNS;
-    let img_width = (COLUMNS as u32) * BLOCK_SIZE_X;
-    let img_height = (rows as u32) * BLOCK_SIZE_Y;
+    let rows = placements.iter().map(|(_, row, _)| row + 1).max().unwrap_or(0) as usize;
+    let img_width = (num_columns as u32) * theme.block_width;
+    let img_height = (rows as u32) * theme.block_height;
 
-    // Draw image w/ a trans background
+    // Draw the canvas background: transparent, or a solid color the swatches sit on top of
     let mut imgbuf: RgbaImage = image::ImageBuffer::new(img_width, img_height);
-    let trans_bg = Rect::at(0,0).of_size(img_width, img_height);
-    draw_filled_rect_mut(&mut imgbuf, trans_bg, Rgba([0u8,0u8,0u8,0u8]));
+    let canvas_bg = match &theme.background {
+        Background::Transparent => Rgba([0u8, 0u8, 0u8, 0u8]),
+        Background::Solid { hex } => {
+            let (r, g, b) = hex_to_rgb(hex);
+            Rgba([r, g, b, 255u8])
+        }
+    };
+    let bg_rect = Rect::at(0, 0).of_size(img_width, img_height);
+    draw_filled_rect_mut(&mut imgbuf, bg_rect, canvas_bg);
+
+    // Load the font: a user-supplied path from the theme, or the embedded JetBrains Mono
+    let font_data = match &theme.font_path {
+        Some(path) => std::fs::read(path).expect("Failed to read font file"),
+        None => include_bytes!("../JetBrainsMono-Regular.ttf").to_vec(),
+    };
+    let font = Font::try_from_bytes(&font_data).expect("Error constructing Font");
 
-    // Load in font & set scale
-    let font_data = include_bytes!("../JetBrainsMono-Regular.ttf");
-    let font = Font::try_from_bytes(font_data as &[u8]).expect("Error constructing Font");
+    let shadow_thickness = theme.shadow_thickness();
 
     // Draw Labeled Color Palette
-    for (i, color) in sorted_colors.iter().enumerate() {
+    for (col, row, color) in placements.iter() {
         let (r, g, b) = hex_to_rgb(&color.hex);
 
-        let col = (i % COLUMNS) as u32;
-        let row = (i / COLUMNS) as u32;
-        
-        let x0 = col * BLOCK_SIZE_X;
-        let y0 = row * BLOCK_SIZE_Y;
+        let col = *col;
+        let row = *row;
 
-        let rect = Rect::at(x0 as i32, y0 as i32).of_size(BLOCK_SIZE_X, BLOCK_SIZE_Y);
-        draw_filled_rect_mut(&mut imgbuf, rect, Rgba([r, g, b, 255u8]));
+        let x0 = col * theme.block_width;
+        let y0 = row * theme.block_height;
 
         let bg_rgb = Srgb::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
-        let (text_r, text_g, text_b) = pick_label_color(bg_rgb);
-        let hsl = Hsl::from_color(bg_rgb);
-        let mut shadow_hsl = hsl;
-        shadow_hsl.lightness = (hsl.lightness - 0.1).max(0.0);
+        let (text_r, text_g, text_b) = pick_label_color(bg_rgb, args.label_contrast_threshold);
+
+        if theme.rounded_swatches {
+            let shadow_color = Rgba([0u8, 0u8, 0u8, 140u8]);
+            shapes::draw_drop_shadow(
+                &mut imgbuf,
+                x0 as i32,
+                y0 as i32,
+                theme.block_width,
+                theme.block_height,
+                theme.corner_radius,
+                theme.shadow_offset_x,
+                theme.shadow_offset_y,
+                theme.shadow_blur_radius,
+                shadow_color,
+            );
+            shapes::draw_rounded_rect(
+                &mut imgbuf,
+                x0 as i32,
+                y0 as i32,
+                theme.block_width,
+                theme.block_height,
+                theme.corner_radius,
+                Rgba([r, g, b, 255u8]),
+            );
+        } else {
+            let rect = Rect::at(x0 as i32, y0 as i32).of_size(theme.block_width, theme.block_height);
+            draw_filled_rect_mut(&mut imgbuf, rect, Rgba([r, g, b, 255u8]));
+
+            let hsl = Hsl::from_color(bg_rgb);
+            let mut shadow_hsl = hsl;
+            shadow_hsl.lightness = (hsl.lightness - theme.shadow_darken_amount).max(0.0);
 
-        let shadow_rgb: Srgb<u8> = Srgb::from_color(shadow_hsl).into_format();
-        let shadow = Rgba([shadow_rgb.red, shadow_rgb.green, shadow_rgb.blue, 255u8]);
+            let shadow_rgb: Srgb<u8> = Srgb::from_color(shadow_hsl).into_format();
+            let shadow = Rgba([shadow_rgb.red, shadow_rgb.green, shadow_rgb.blue, 255u8]);
 
-        let rect = Rect::at(x0 as i32, (y0 + BLOCK_SIZE_Y - BLOCK_SIZE_Y/20) as i32).of_size(BLOCK_SIZE_X, BLOCK_SIZE_Y/20);
-        draw_filled_rect_mut(&mut imgbuf, rect, shadow);
+            if shadow_thickness > 0 {
+                let rect = Rect::at(x0 as i32, (y0 + theme.block_height - shadow_thickness) as i32)
+                    .of_size(theme.block_width, shadow_thickness);
+                draw_filled_rect_mut(&mut imgbuf, rect, shadow);
+            }
+        }
 
-        let name_rect = Rect::at(x0 as i32, y0  as i32).of_size(BLOCK_SIZE_X, BLOCK_SIZE_Y);
-        let hex_rect = Rect::at(x0 as i32, y0 as i32 + (BLOCK_SIZE_Y as f32/3.25) as i32).of_size(BLOCK_SIZE_X, BLOCK_SIZE_Y);
+        let name_rect = Rect::at(x0 as i32, y0 as i32).of_size(theme.block_width, theme.block_height);
+        let hex_rect = Rect::at(x0 as i32, y0 as i32 + (theme.block_height as f32 / 3.25) as i32)
+            .of_size(theme.block_width, theme.block_height);
         let base_color = Rgba([r, g, b, 255]);
         let text_color = Rgba([text_r, text_g, text_b, 255]);
 
         /*println!("Color: {:^10} | H: {:>10} | S: {:>10} | L:{:>10}",color.name, hsl.hue.into_degrees().abs(), hsl.saturation, hsl.lightness);*/
-        
-        draw_centered_text(&mut imgbuf, &font, &color.name, name_rect, base_color, text_color, 3.5, -10);
-        draw_centered_text(&mut imgbuf, &font, &color.hex, hex_rect, base_color, text_color, 6.5, 0-(BLOCK_SIZE_Y/20) as i32);
+
+        if theme.draw_name {
+            draw_centered_text(&mut imgbuf, &font, &color.name, name_rect, base_color, text_color, 3.5, theme.name_vertical_offset);
+        }
+        if theme.draw_hex {
+            draw_centered_text(&mut imgbuf, &font, &color.hex, hex_rect, base_color, text_color, 6.5, theme.hex_vertical_offset);
+        }
     }
     let output_file = std::path::PathBuf::from(&args.output);
     imgbuf.save(output_file).expect("Failed to save image");
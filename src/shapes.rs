@@ -0,0 +1,161 @@
+use image::{Rgba, RgbaImage};
+
+/// Coverage in `[0, 1]` of a pixel at `(dx, dy)` within a `w`×`h` rounded
+/// rect whose corners have radius `r`: full coverage outside the four
+/// corner squares, and a Wu-style fractional coverage inside them based on
+/// how far the pixel center sits from the ideal quarter-circle arc.
+fn rounded_rect_coverage(dx: i32, dy: i32, w: i32, h: i32, r: i32) -> f32 {
+    if r <= 0 {
+        return 1.0;
+    }
+
+    let in_corner_x = dx < r || dx >= w - r;
+    let in_corner_y = dy < r || dy >= h - r;
+    if !(in_corner_x && in_corner_y) {
+        return 1.0;
+    }
+
+    let cx = if dx < r { r } else { w - r - 1 };
+    let cy = if dy < r { r } else { h - r - 1 };
+    let dist = (((dx - cx).pow(2) + (dy - cy).pow(2)) as f32).sqrt();
+
+    (r as f32 + 0.5 - dist).clamp(0.0, 1.0)
+}
+
+/// Alpha-composites `color` (which may itself carry a non-opaque alpha)
+/// over the pixel at `(x, y)`, scaling its alpha by `coverage`.
+fn blend_pixel(imgbuf: &mut RgbaImage, x: u32, y: u32, color: Rgba<u8>, coverage: f32) {
+    let src_alpha = coverage * (color[3] as f32 / 255.0);
+    if src_alpha <= 0.0 {
+        return;
+    }
+
+    let existing = *imgbuf.get_pixel(x, y);
+    let dst_alpha = existing[3] as f32 / 255.0;
+    let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+
+    let blend_channel = |src_c: u8, dst_c: u8| -> u8 {
+        if out_alpha <= 0.0 {
+            return 0;
+        }
+        let src_c = src_c as f32 / 255.0;
+        let dst_c = dst_c as f32 / 255.0;
+        (((src_c * src_alpha) + (dst_c * dst_alpha * (1.0 - src_alpha))) / out_alpha * 255.0).round() as u8
+    };
+
+    imgbuf.put_pixel(
+        x,
+        y,
+        Rgba([
+            blend_channel(color[0], existing[0]),
+            blend_channel(color[1], existing[1]),
+            blend_channel(color[2], existing[2]),
+            (out_alpha * 255.0).round() as u8,
+        ]),
+    );
+}
+
+/// Fills a rounded rectangle: the interior rect plus four quarter-circle
+/// corners, anti-aliased by blending `color` into the existing pixel by
+/// each corner pixel's fractional coverage rather than a hard 0/255 cutoff.
+pub fn draw_rounded_rect(imgbuf: &mut RgbaImage, x0: i32, y0: i32, width: u32, height: u32, radius: u32, color: Rgba<u8>) {
+    let radius = radius.min(width / 2).min(height / 2) as i32;
+    let (w, h) = (width as i32, height as i32);
+
+    for dy in 0..h {
+        for dx in 0..w {
+            let coverage = rounded_rect_coverage(dx, dy, w, h, radius);
+            if coverage <= 0.0 {
+                continue;
+            }
+
+            let (px, py) = (x0 + dx, y0 + dy);
+            if px < 0 || py < 0 || px as u32 >= imgbuf.width() || py as u32 >= imgbuf.height() {
+                continue;
+            }
+            blend_pixel(imgbuf, px as u32, py as u32, color, coverage);
+        }
+    }
+}
+
+/// Single-axis box blur over a flattened `width`×`height` buffer: each
+/// output pixel is the average of its `2*radius + 1` neighbors along that
+/// axis; near the edges only the in-bounds neighbors are averaged.
+fn box_blur_pass(src: &[f32], width: i32, height: i32, radius: i32, horizontal: bool) -> Vec<f32> {
+    let mut out = vec![0.0f32; src.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = 0.0;
+            let mut count = 0;
+            for offset in -radius..=radius {
+                let (sx, sy) = if horizontal { (x + offset, y) } else { (x, y + offset) };
+                if sx >= 0 && sx < width && sy >= 0 && sy < height {
+                    sum += src[(sy * width + sx) as usize];
+                    count += 1;
+                }
+            }
+            out[(y * width + x) as usize] = sum / count as f32;
+        }
+    }
+    out
+}
+
+/// Separable box blur: one horizontal pass followed by one vertical pass.
+fn box_blur(src: &[f32], width: i32, height: i32, radius: i32) -> Vec<f32> {
+    let horizontal = box_blur_pass(src, width, height, radius, true);
+    box_blur_pass(&horizontal, width, height, radius, false)
+}
+
+/// Draws a soft drop shadow for a `width`×`height` rounded rect at
+/// `(x0, y0)`: the swatch silhouette's coverage is rendered into an alpha
+/// buffer padded by `blur_radius + 1`, smoothed with a separable box blur, then
+/// composited offset by `(shadow_dx, shadow_dy)` using `color`'s RGB and
+/// the blurred coverage scaled by `color`'s own alpha.
+pub fn draw_drop_shadow(
+    imgbuf: &mut RgbaImage,
+    x0: i32,
+    y0: i32,
+    width: u32,
+    height: u32,
+    radius: u32,
+    shadow_dx: i32,
+    shadow_dy: i32,
+    blur_radius: u32,
+    color: Rgba<u8>,
+) {
+    let pad = blur_radius as i32 + 1;
+    let canvas_w = width as i32 + pad * 2;
+    let canvas_h = height as i32 + pad * 2;
+    let r = radius.min(width / 2).min(height / 2) as i32;
+
+    let mut alpha = vec![0.0f32; (canvas_w * canvas_h) as usize];
+    for dy in 0..height as i32 {
+        for dx in 0..width as i32 {
+            let coverage = rounded_rect_coverage(dx, dy, width as i32, height as i32, r);
+            if coverage > 0.0 {
+                let (ax, ay) = (dx + pad, dy + pad);
+                alpha[(ay * canvas_w + ax) as usize] = coverage;
+            }
+        }
+    }
+
+    if blur_radius > 0 {
+        alpha = box_blur(&alpha, canvas_w, canvas_h, blur_radius as i32);
+    }
+
+    for ay in 0..canvas_h {
+        for ax in 0..canvas_w {
+            let coverage = alpha[(ay * canvas_w + ax) as usize];
+            if coverage <= 0.0 {
+                continue;
+            }
+
+            let px = x0 + shadow_dx + ax - pad;
+            let py = y0 + shadow_dy + ay - pad;
+            if px < 0 || py < 0 || px as u32 >= imgbuf.width() || py as u32 >= imgbuf.height() {
+                continue;
+            }
+            blend_pixel(imgbuf, px as u32, py as u32, color, coverage);
+        }
+    }
+}
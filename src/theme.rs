@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Output canvas background: either fully transparent, or a solid color
+/// that swatches and labels are composited over.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "lowercase", tag = "kind")]
+pub enum Background {
+    Transparent,
+    Solid { hex: String },
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Background::Transparent
+    }
+}
+
+/// Visual parameters that used to be compile-time constants: block
+/// dimensions, column count, background, font, label offsets, shadow
+/// styling, and which labels to draw. Defaults match the tool's original,
+/// pre-theming behavior.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub block_width: u32,
+    pub block_height: u32,
+    pub columns: usize,
+    pub background: Background,
+    /// Path to a TTF/OTF font loaded at runtime; falls back to the
+    /// embedded JetBrains Mono when unset.
+    pub font_path: Option<PathBuf>,
+    pub name_vertical_offset: i32,
+    pub hex_vertical_offset: i32,
+    /// Shadow bar thickness, as a fraction of `block_height`.
+    pub shadow_thickness_fraction: f32,
+    /// How much darker the shadow bar is than the swatch, in HSL lightness.
+    pub shadow_darken_amount: f32,
+    pub draw_name: bool,
+    pub draw_hex: bool,
+    /// Draw anti-aliased rounded-corner swatches with a soft drop shadow
+    /// instead of hard-edged rectangles with a flat bottom shadow bar.
+    pub rounded_swatches: bool,
+    pub corner_radius: u32,
+    pub shadow_offset_x: i32,
+    pub shadow_offset_y: i32,
+    pub shadow_blur_radius: u32,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            block_width: 400,
+            block_height: 300,
+            columns: 8,
+            background: Background::default(),
+            font_path: None,
+            name_vertical_offset: -10,
+            hex_vertical_offset: -15,
+            shadow_thickness_fraction: 0.05,
+            shadow_darken_amount: 0.1,
+            draw_name: true,
+            draw_hex: true,
+            rounded_swatches: false,
+            corner_radius: 24,
+            shadow_offset_x: 6,
+            shadow_offset_y: 6,
+            shadow_blur_radius: 8,
+        }
+    }
+}
+
+impl Theme {
+    /// Loads a `Theme` from a TOML or JSON config file, falling back to
+    /// `Theme::default()` when no path is given.
+    pub fn load(path: Option<&Path>) -> Theme {
+        let theme = match path {
+            None => Theme::default(),
+            Some(path) => {
+                let contents = std::fs::read_to_string(path).expect("Failed to read theme config");
+                match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()).as_deref() {
+                    Some("json") => serde_json::from_str(&contents).expect("Failed to parse theme JSON"),
+                    _ => toml::from_str(&contents).expect("Failed to parse theme TOML"),
+                }
+            }
+        };
+        theme.validate()
+    }
+
+    /// Rejects degenerate values that would otherwise crash layout/drawing
+    /// further down (a zero `columns`/`block_width`/`block_height` feeds a
+    /// zero straight into a modulus or `Rect::of_size`). `shadow_thickness_fraction
+    /// == 0.0` is kept legal — it's the natural way to ask for no shadow bar at
+    /// all, and drawing already skips the shadow rect when its thickness is 0.
+    fn validate(self) -> Theme {
+        assert!(self.columns > 0, "theme `columns` must be at least 1");
+        assert!(self.block_width > 0, "theme `block_width` must be strictly positive");
+        assert!(self.block_height > 0, "theme `block_height` must be strictly positive");
+        assert!(
+            (0.0..1.0).contains(&self.shadow_thickness_fraction),
+            "theme `shadow_thickness_fraction` must be within [0, 1)"
+        );
+        self
+    }
+
+    pub fn shadow_thickness(&self) -> u32 {
+        ((self.block_height as f32) * self.shadow_thickness_fraction) as u32
+    }
+}